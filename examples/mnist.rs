@@ -0,0 +1,103 @@
+use std::fs::File;
+use std::io::Read;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use nn::loss::{cross_entropy, softmax};
+use nn::nn::{Activation, Mlp};
+use nn::optim::{Adam, Optimizer};
+
+const IMAGE_MAGIC: u32 = 0x00000803;
+const LABEL_MAGIC: u32 = 0x00000801;
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let images = read_images("train-images-idx3-ubyte");
+    let labels = read_labels("train-labels-idx1-ubyte");
+    let mut examples: Vec<_> = images.into_iter().zip(labels).collect();
+    examples.shuffle(&mut rng);
+    let (training_set, test_set) = examples.split_at(examples.len() * 9 / 10);
+
+    let model = Mlp::new(784, vec![(64, Activation::Relu), (10, Activation::Identity)], &mut rng);
+    let mut optimizer = Adam::new(0.001, 0.9, 0.999, 1e-8);
+
+    println!("Training set gradient descent");
+    let number_of_epochs = 5;
+    let batch_size = 32;
+    for epoch in 0..number_of_epochs {
+        let mut shuffled = training_set.to_vec();
+        shuffled.shuffle(&mut rng);
+
+        for batch in shuffled.chunks(batch_size) {
+            let mut loss = nn::autograd::Tensor::new(0.0);
+            let inputs: Vec<_> = batch.iter().map(|(pixels, _)| pixels.clone()).collect();
+            let outputs = model.process(&inputs);
+            for ((_, label), logits) in batch.iter().zip(&outputs) {
+                let probabilities = softmax(logits);
+                loss = loss.add(&cross_entropy(&probabilities, *label));
+            }
+            loss = loss.mul(&nn::autograd::Tensor::new(1.0).div(&nn::autograd::Tensor::new(batch.len() as f64)));
+
+            model.zero_grad();
+            loss.backward();
+            optimizer.step(&model.parameters());
+        }
+
+        let accuracy = accuracy(&model, training_set);
+        println!("Epoch {}, training accuracy: {}%", epoch, accuracy);
+    }
+
+    println!("Test set inference");
+    let accuracy = accuracy(&model, test_set);
+    println!("Accuracy: {}%", accuracy);
+}
+
+fn accuracy(model: &Mlp, examples: &[(Vec<f64>, usize)]) -> f64 {
+    let inputs: Vec<_> = examples.iter().map(|(pixels, _)| pixels.clone()).collect();
+    let outputs = model.process(&inputs);
+    let matches = examples.iter().zip(&outputs)
+        .filter(|((_, label), logits)| argmax(logits) == *label)
+        .count();
+    matches as f64 / examples.len() as f64 * 100.0
+}
+
+fn argmax(logits: &[nn::autograd::Tensor]) -> usize {
+    logits.iter().enumerate()
+        .max_by(|(_, a), (_, b)| a.data.borrow().total_cmp(&b.data.borrow()))
+        .map(|(i, _)| i)
+        .unwrap()
+}
+
+fn read_images(path: &str) -> Vec<Vec<f64>> {
+    let bytes = read_file(path);
+    assert_eq!(be_u32(&bytes, 0), IMAGE_MAGIC, "unexpected magic number in {}", path);
+    let count = be_u32(&bytes, 4) as usize;
+    let rows = be_u32(&bytes, 8) as usize;
+    let cols = be_u32(&bytes, 12) as usize;
+    assert_eq!((rows, cols), (28, 28), "unexpected image dimensions in {}", path);
+    let image_size = rows * cols;
+
+    let pixels = &bytes[16..];
+    (0..count).map(|i| {
+        pixels[i * image_size..(i + 1) * image_size].iter().map(|&b| b as f64 / 255.0).collect()
+    }).collect()
+}
+
+fn read_labels(path: &str) -> Vec<usize> {
+    let bytes = read_file(path);
+    assert_eq!(be_u32(&bytes, 0), LABEL_MAGIC, "unexpected magic number in {}", path);
+    let count = be_u32(&bytes, 4) as usize;
+
+    bytes[8..8 + count].iter().map(|&b| b as usize).collect()
+}
+
+fn read_file(path: &str) -> Vec<u8> {
+    let mut bytes = vec![];
+    File::open(path).unwrap().read_to_end(&mut bytes).unwrap();
+    bytes
+}
+
+fn be_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
@@ -0,0 +1,6 @@
+pub mod autograd;
+pub mod loss;
+pub mod matrix;
+pub mod nn;
+pub mod optim;
+pub mod plot;
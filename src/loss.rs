@@ -0,0 +1,86 @@
+use crate::autograd::Tensor;
+
+pub fn softmax(logits: &[Tensor]) -> Vec<Tensor> {
+    softmax_with_denominator_offset(logits, 0.0)
+}
+
+/// Like `softmax`, but reserves some probability mass for "no class is confident" by adding `1`
+/// to the denominator instead of forcing the logits into a fully-confident distribution. A
+/// building block for classifiers that need to express uncertainty (e.g. a decision boundary
+/// region); not wired into any example in this crate yet.
+pub fn quiet_softmax(logits: &[Tensor]) -> Vec<Tensor> {
+    softmax_with_denominator_offset(logits, 1.0)
+}
+
+pub fn cross_entropy(probabilities: &[Tensor], target: usize) -> Tensor {
+    Tensor::new(-1.0).mul(&probabilities[target].ln())
+}
+
+fn softmax_with_denominator_offset(logits: &[Tensor], offset: f64) -> Vec<Tensor> {
+    let max = logits.iter().map(|l| *l.data.borrow()).fold(f64::NEG_INFINITY, f64::max);
+    let shifted: Vec<_> = logits.iter().map(|l| l.sub(&Tensor::new(max)).exp()).collect();
+    // The logits are shifted by `max`, so the offset is scaled by exp(-max) to keep it added to
+    // the unshifted sum of exp(z_j).
+    let scaled_offset = if offset == 0.0 { 0.0 } else { offset * (-max).exp() };
+    let mut denominator = Tensor::new(scaled_offset);
+    for s in &shifted {
+        denominator = denominator.add(s);
+    }
+    shifted.iter().map(|s| s.div(&denominator)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `div` computes a reciprocal via `powf`, which can differ from the mathematically exact
+    /// value in the last ULP, so probabilities are compared with a relative-error tolerance
+    /// instead of exact float equality (mirrors `check_gradient` in `autograd`).
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        let relative_error = (actual - expected).abs() / expected.abs().max(1e-12);
+        assert!(relative_error < tolerance, "{} is not within {} of {}", actual, tolerance, expected);
+    }
+
+    #[test]
+    fn softmax_should_normalize_logits_into_a_probability_distribution() {
+        let logits = vec![Tensor::new(1.0), Tensor::new(2.0), Tensor::new(3.0)];
+
+        let probabilities = softmax(&logits);
+
+        let values: Vec<_> = probabilities.iter().map(|p| *p.data.borrow()).collect();
+        let expected = [0.09003057317038046, 0.24472847105479767, 0.6652409557748219];
+        for (value, expected) in values.iter().zip(expected) {
+            assert_close(*value, expected, 1e-9);
+        }
+        assert_close(values.iter().sum::<f64>(), 1.0, 1e-9);
+    }
+
+    #[test]
+    fn quiet_softmax_should_leave_room_for_an_unconfident_distribution() {
+        let logits = vec![Tensor::new(1.0), Tensor::new(2.0), Tensor::new(3.0)];
+
+        let probabilities = quiet_softmax(&logits);
+
+        let values: Vec<_> = probabilities.iter().map(|p| *p.data.borrow()).collect();
+        assert!(values.iter().sum::<f64>() < 1.0);
+    }
+
+    #[test]
+    fn cross_entropy_should_penalize_low_probability_on_the_target_class() {
+        let probabilities = vec![Tensor::new(0.7), Tensor::new(0.2), Tensor::new(0.1)];
+
+        let loss = cross_entropy(&probabilities, 2);
+
+        assert_eq!(*loss.data.borrow(), -0.1_f64.ln());
+    }
+
+    #[test]
+    fn cross_entropy_should_backpropagate_to_the_target_probability() {
+        let probabilities = vec![Tensor::new(0.7), Tensor::new(0.2), Tensor::new(0.1)];
+
+        let loss = cross_entropy(&probabilities, 0);
+        loss.backward();
+
+        assert_eq!(*probabilities[0].grad.borrow(), -1.0 / 0.7);
+    }
+}
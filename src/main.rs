@@ -4,19 +4,17 @@ use std::io::{BufRead, BufReader};
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use rand::seq::SliceRandom;
-use crate::autograd::Tensor;
-use crate::nn::Mlp;
-use crate::plot::{plot_decision_boundary, plot_classification};
-
-mod autograd;
-mod nn;
-mod plot;
+use nn::autograd::Tensor;
+use nn::nn::{Activation, Mlp};
+use nn::optim::{Adam, Optimizer};
+use nn::plot::{plot_decision_boundary, plot_classification};
 
 fn main() {
     let mut rng = StdRng::seed_from_u64(42);
-    let model = Mlp::new(2, vec![16, 16, 1], &mut rng);
+    let model = Mlp::new(2, vec![(16, Activation::Tanh), (16, Activation::Tanh), (1, Activation::Tanh)], &mut rng);
     let make_moons = make_moons(&mut rng);
     let (training_set, test_set) = make_moons.split_at(100);
+    let mut optimizer = Adam::new(0.1, 0.9, 0.999, 1e-8);
 
     println!("Training set gradient descent");
     let number_of_iterations = 100;
@@ -25,8 +23,10 @@ fn main() {
         let mut scores = vec![];
 
         //forward pass
-        for (x, y, label) in training_set {
-            let score = &model.process(&[*x, *y])[0];
+        let inputs: Vec<_> = training_set.iter().map(|(x, y, _)| vec![*x, *y]).collect();
+        let outputs = model.process(&inputs);
+        for ((x, y, label), output) in training_set.iter().zip(&outputs) {
+            let score = &output[0];
             loss = loss.add(&Tensor::new(*label).sub(score).pow(2.0));
             scores.push((*x, *y, *score.data.borrow()));
         }
@@ -40,17 +40,15 @@ fn main() {
         loss.backward();
 
         //update
-        let learning_rate = 1.0 - 0.9 * (k as f64) / number_of_iterations as f64;
-        for p in model.parameters() {
-            *p.data.borrow_mut() -= *p.grad.borrow() * learning_rate;
-        }
+        optimizer.step(&model.parameters());
     }
 
     println!("Test set inference");
     let mut scores = vec![];
-    for (x, y, _) in test_set {
-        let score = &model.process(&[*x, *y])[0];
-        scores.push((*x, *y, *score.data.borrow()));
+    let inputs: Vec<_> = test_set.iter().map(|(x, y, _)| vec![*x, *y]).collect();
+    let outputs = model.process(&inputs);
+    for ((x, y, _), output) in test_set.iter().zip(&outputs) {
+        scores.push((*x, *y, *output[0].data.borrow()));
     }
 
     let accuracy = accuracy(test_set, &scores);
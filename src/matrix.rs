@@ -0,0 +1,331 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+use uuid::Uuid;
+use crate::nn::Activation;
+
+/// A batched, matrix-valued counterpart to `autograd::Tensor`. A scalar `Tensor` builds one
+/// graph node per multiply-add, so a forward pass over a whole batch is thousands of tiny
+/// nodes; `Matrix` instead holds a whole `[rows, cols]` batch in one node and exposes a
+/// `matmul` op, collapsing a layer's forward pass to a single node.
+pub struct Matrix(Rc<MatrixValue>);
+
+pub struct MatrixValue {
+    pub data: RefCell<Vec<f64>>,
+    pub shape: (usize, usize),
+    label: Uuid,
+    grad: RefCell<Vec<f64>>,
+    back: Op,
+}
+
+enum Op {
+    None,
+    Add { x: Rc<MatrixValue>, y: Rc<MatrixValue> },
+    AddBiasRow { x: Rc<MatrixValue>, y: Rc<MatrixValue> },
+    MatMul { x: Rc<MatrixValue>, y: Rc<MatrixValue> },
+    Activation { x: Rc<MatrixValue>, activation: Activation },
+}
+
+impl Matrix {
+    pub fn new(data: Vec<f64>, shape: (usize, usize)) -> Matrix {
+        assert_eq!(data.len(), shape.0 * shape.1, "data does not match shape");
+        Matrix(value(data, shape))
+    }
+
+    pub fn zeros(shape: (usize, usize)) -> Matrix {
+        Matrix::new(vec![0.0; shape.0 * shape.1], shape)
+    }
+
+    pub fn add(&self, other: &Matrix) -> Matrix {
+        assert_eq!(self.shape, other.shape, "add requires matching shapes");
+        let data = self.data.borrow().iter().zip(other.data.borrow().iter()).map(|(a, b)| a + b).collect();
+        Matrix(Rc::new(MatrixValue {
+            label: Uuid::new_v4(),
+            data: RefCell::new(data),
+            shape: self.shape,
+            grad: RefCell::new(vec![0.0; self.shape.0 * self.shape.1]),
+            back: Op::Add { x: Rc::clone(self), y: Rc::clone(other) },
+        }))
+    }
+
+    /// Adds a `[1, cols]` bias row to every row of `self`, broadcasting it across the batch.
+    pub fn add_bias_row(&self, bias: &Matrix) -> Matrix {
+        assert_eq!(bias.shape, (1, self.shape.1), "bias must be a single row matching the column count");
+        let (rows, cols) = self.shape;
+        let bias_data = bias.data.borrow();
+        let data = self.data.borrow().iter().enumerate()
+            .map(|(i, x)| x + bias_data[i % cols]).collect();
+        Matrix(Rc::new(MatrixValue {
+            label: Uuid::new_v4(),
+            data: RefCell::new(data),
+            shape: (rows, cols),
+            grad: RefCell::new(vec![0.0; rows * cols]),
+            back: Op::AddBiasRow { x: Rc::clone(self), y: Rc::clone(bias) },
+        }))
+    }
+
+    pub fn matmul(&self, other: &Matrix) -> Matrix {
+        assert_eq!(self.shape.1, other.shape.0, "matmul requires inner dimensions to match");
+        let shape = (self.shape.0, other.shape.1);
+        let data = matmul_raw(&self.data.borrow(), self.shape, &other.data.borrow(), other.shape);
+        Matrix(Rc::new(MatrixValue {
+            label: Uuid::new_v4(),
+            data: RefCell::new(data),
+            shape,
+            grad: RefCell::new(vec![0.0; shape.0 * shape.1]),
+            back: Op::MatMul { x: Rc::clone(self), y: Rc::clone(other) },
+        }))
+    }
+
+    pub fn tanh(&self) -> Matrix {
+        self.activation(Activation::Tanh)
+    }
+
+    pub fn relu(&self) -> Matrix {
+        self.activation(Activation::Relu)
+    }
+
+    pub fn sigmoid(&self) -> Matrix {
+        self.activation(Activation::Sigmoid)
+    }
+
+    fn activation(&self, activation: Activation) -> Matrix {
+        let data = self.data.borrow().iter().map(|x| apply_activation(activation, *x)).collect();
+        Matrix(Rc::new(MatrixValue {
+            label: Uuid::new_v4(),
+            data: RefCell::new(data),
+            shape: self.shape,
+            grad: RefCell::new(vec![0.0; self.shape.0 * self.shape.1]),
+            back: Op::Activation { x: Rc::clone(self), activation },
+        }))
+    }
+
+    pub fn backward(&self) {
+        *self.grad.borrow_mut() = vec![1.0; self.shape.0 * self.shape.1];
+        let mut topo = vec![];
+        topological_sort(self, &mut topo, &mut HashSet::new());
+
+        for v in topo.iter().rev() {
+            backward_step(v);
+        }
+    }
+}
+
+impl std::ops::Deref for Matrix {
+    type Target = Rc<MatrixValue>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+fn value(data: Vec<f64>, shape: (usize, usize)) -> Rc<MatrixValue> {
+    Rc::new(MatrixValue {
+        label: Uuid::new_v4(),
+        grad: RefCell::new(vec![0.0; data.len()]),
+        data: RefCell::new(data),
+        shape,
+        back: Op::None,
+    })
+}
+
+fn apply_activation(activation: Activation, x: f64) -> f64 {
+    match activation {
+        Activation::Tanh => x.tanh(),
+        Activation::Relu => x.max(0.0),
+        Activation::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+        Activation::Identity => x,
+    }
+}
+
+fn activation_local_derivative(activation: Activation, x: f64, y: f64) -> f64 {
+    match activation {
+        Activation::Tanh => 1.0 - y.powi(2),
+        Activation::Relu => if x > 0.0 { 1.0 } else { 0.0 },
+        Activation::Sigmoid => y * (1.0 - y),
+        Activation::Identity => 1.0,
+    }
+}
+
+fn matmul_raw(a: &[f64], a_shape: (usize, usize), b: &[f64], b_shape: (usize, usize)) -> Vec<f64> {
+    let (m, k) = a_shape;
+    let (_, n) = b_shape;
+    let mut out = vec![0.0; m * n];
+    for i in 0..m {
+        for p in 0..k {
+            let a_ip = a[i * k + p];
+            for j in 0..n {
+                out[i * n + j] += a_ip * b[p * n + j];
+            }
+        }
+    }
+    out
+}
+
+fn transpose_raw(a: &[f64], shape: (usize, usize)) -> (Vec<f64>, (usize, usize)) {
+    let (rows, cols) = shape;
+    let mut out = vec![0.0; a.len()];
+    for i in 0..rows {
+        for j in 0..cols {
+            out[j * rows + i] = a[i * cols + j];
+        }
+    }
+    (out, (cols, rows))
+}
+
+fn prev(value: &Rc<MatrixValue>) -> Vec<&Rc<MatrixValue>> {
+    match &value.back {
+        Op::None => vec![],
+        Op::Add { x, y } => vec![x, y],
+        Op::AddBiasRow { x, y } => vec![x, y],
+        Op::MatMul { x, y } => vec![x, y],
+        Op::Activation { x, .. } => vec![x],
+    }
+}
+
+fn topological_sort<'a>(value: &'a Rc<MatrixValue>, topo: &mut Vec<&'a Rc<MatrixValue>>, visited: &mut HashSet<Uuid>) {
+    if !visited.contains(&value.label) {
+        visited.insert(value.label);
+        for v in prev(value) {
+            topological_sort(v, topo, visited)
+        }
+        topo.push(value)
+    }
+}
+
+fn backward_step(value: &Rc<MatrixValue>) {
+    match &value.back {
+        Op::None => {}
+        Op::Add { x, y } => {
+            let value_grad = value.grad.borrow();
+            let mut x_grad = x.grad.borrow_mut();
+            let mut y_grad = y.grad.borrow_mut();
+            for i in 0..value_grad.len() {
+                x_grad[i] += value_grad[i];
+                y_grad[i] += value_grad[i];
+            }
+        }
+        Op::AddBiasRow { x, y } => {
+            let (rows, cols) = x.shape;
+            let value_grad = value.grad.borrow();
+            let mut x_grad = x.grad.borrow_mut();
+            let mut y_grad = y.grad.borrow_mut();
+            for i in 0..rows {
+                for j in 0..cols {
+                    let g = value_grad[i * cols + j];
+                    x_grad[i * cols + j] += g;
+                    y_grad[j] += g;
+                }
+            }
+        }
+        Op::MatMul { x, y } => {
+            let value_grad = value.grad.borrow();
+            let (y_data_t, y_data_t_shape) = transpose_raw(&y.data.borrow(), y.shape);
+            let (x_data_t, x_data_t_shape) = transpose_raw(&x.data.borrow(), x.shape);
+            let dx = matmul_raw(&value_grad, value.shape, &y_data_t, y_data_t_shape);
+            let dy = matmul_raw(&x_data_t, x_data_t_shape, &value_grad, value.shape);
+            let mut x_grad = x.grad.borrow_mut();
+            let mut y_grad = y.grad.borrow_mut();
+            for i in 0..dx.len() {
+                x_grad[i] += dx[i];
+            }
+            for i in 0..dy.len() {
+                y_grad[i] += dy[i];
+            }
+        }
+        Op::Activation { x, activation } => {
+            let value_data = value.data.borrow();
+            let value_grad = value.grad.borrow();
+            let x_data = x.data.borrow();
+            let mut x_grad = x.grad.borrow_mut();
+            for i in 0..x_grad.len() {
+                let local_derivative = activation_local_derivative(*activation, x_data[i], value_data[i]);
+                x_grad[i] += local_derivative * value_grad[i];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use crate::nn::Mlp;
+
+    #[test]
+    fn matmul_should_compute_the_matrix_product() {
+        let a = Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], (2, 3));
+        let b = Matrix::new(vec![7.0, 8.0, 9.0, 10.0, 11.0, 12.0], (3, 2));
+
+        let c = a.matmul(&b);
+
+        assert_eq!(*c.data.borrow(), vec![58.0, 64.0, 139.0, 154.0]);
+        assert_eq!(c.shape, (2, 2));
+    }
+
+    #[test]
+    fn matmul_backward_should_compute_gradients_for_both_operands() {
+        let a = Matrix::new(vec![1.0, 2.0, 3.0, 4.0], (2, 2));
+        let b = Matrix::new(vec![5.0, 6.0, 7.0, 8.0], (2, 2));
+
+        let c = a.matmul(&b);
+        c.backward();
+
+        // dA = dC . B^T with dC = ones, dB = A^T . dC with dC = ones
+        assert_eq!(*a.grad.borrow(), vec![11.0, 15.0, 11.0, 15.0]);
+        assert_eq!(*b.grad.borrow(), vec![4.0, 4.0, 6.0, 6.0]);
+    }
+
+    #[test]
+    fn add_bias_row_should_broadcast_the_bias_over_every_row() {
+        let x = Matrix::new(vec![1.0, 2.0, 3.0, 4.0], (2, 2));
+        let bias = Matrix::new(vec![10.0, 20.0], (1, 2));
+
+        let y = x.add_bias_row(&bias);
+
+        assert_eq!(*y.data.borrow(), vec![11.0, 22.0, 13.0, 24.0]);
+    }
+
+    #[test]
+    fn add_bias_row_backward_should_sum_gradients_into_the_bias_row() {
+        let x = Matrix::new(vec![1.0, 2.0, 3.0, 4.0], (2, 2));
+        let bias = Matrix::new(vec![10.0, 20.0], (1, 2));
+
+        let y = x.add_bias_row(&bias);
+        y.backward();
+
+        assert_eq!(*bias.grad.borrow(), vec![2.0, 2.0]);
+    }
+
+    #[test]
+    fn batched_matmul_forward_pass_demonstrates_gflops_style_throughput() {
+        let batch_size: usize = 200;
+        let number_of_inputs: usize = 32;
+        let number_of_neurons: usize = 32;
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let mlp = Mlp::new_all_tanh(number_of_inputs as i32, vec![number_of_neurons as i32], &mut rng);
+        let inputs: Vec<Vec<f64>> = (0..batch_size)
+            .map(|_| (0..number_of_inputs).map(|_| rng.gen()).collect())
+            .collect();
+
+        let one_at_a_time_start = Instant::now();
+        for row in &inputs {
+            mlp.process(std::slice::from_ref(row));
+        }
+        let one_at_a_time_elapsed = one_at_a_time_start.elapsed();
+
+        let batched_start = Instant::now();
+        let _ = mlp.process(&inputs);
+        let batched_elapsed = batched_start.elapsed();
+
+        // Wall-clock deltas are too noisy under CI load to assert an ordering on, so this is a
+        // println!-style benchmark rather than a pass/fail check.
+        let flops = 2 * batch_size * number_of_inputs * number_of_neurons;
+        println!(
+            "one example at a time: {:?}; single batched matmul over {} examples: {:?} ({:.3} GFLOP/s)",
+            one_at_a_time_elapsed, batch_size, batched_elapsed, flops as f64 / batched_elapsed.as_secs_f64() / 1e9,
+        );
+    }
+}
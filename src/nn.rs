@@ -1,66 +1,100 @@
 use crate::autograd::Tensor;
+use crate::matrix::Matrix;
 use rand::Rng;
 use rand::distributions::Uniform;
 use rand::rngs::StdRng;
 
-struct Neuron {
-    weights: Vec<Tensor>,
-    bias: Tensor,
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Activation {
+    Tanh,
+    Relu,
+    Sigmoid,
+    Identity,
+}
+
+impl Activation {
+    fn apply(&self, x: &Tensor) -> Tensor {
+        match self {
+            Activation::Tanh => x.tanh(),
+            Activation::Relu => x.relu(),
+            Activation::Sigmoid => x.sigmoid(),
+            Activation::Identity => Tensor::clone(x),
+        }
+    }
 }
 
 struct Layer {
-    neurons: Vec<Neuron>,
+    weights: Vec<Vec<Tensor>>,
+    biases: Vec<Tensor>,
+    activation: Activation,
 }
 
 pub struct Mlp {
     layers: Vec<Layer>
 }
 
-impl Neuron {
-    fn new(number_of_inputs: i32, rng: &mut StdRng) -> Neuron {
-        let weights = (0..number_of_inputs).map(|_| Tensor::new(rng.sample(rng_range()))).collect();
-        let bias = Tensor::new(rng.sample(rng_range()));
-        Neuron { weights, bias }
-    }
-
-    fn process(&self, inputs: &[Tensor]) -> Tensor {
-        let mut sum = Tensor::new(0.0);
-        for (wi, xi) in self.weights.iter().zip(inputs) {
-            sum = sum.add(&wi.mul(xi));
-        }
-        sum.add(&self.bias).tanh()
-    }
-
-    fn parameters(&self) -> Vec<Tensor> {
-        [self.weights.iter().map(Tensor::clone).collect(), vec![Tensor::clone(&self.bias)]].concat()
-    }
-}
-
 impl Layer {
-    fn new(number_of_inputs: i32, number_of_neurons: i32, rng: &mut StdRng) -> Layer {
-        Layer { neurons: (0..number_of_neurons).map(|_| Neuron::new(number_of_inputs, rng)).collect()}
+    fn new(number_of_inputs: i32, number_of_neurons: i32, activation: Activation, rng: &mut StdRng) -> Layer {
+        let (weights, biases): (Vec<_>, Vec<_>) = (0..number_of_neurons).map(|_| {
+            let weights: Vec<_> = (0..number_of_inputs).map(|_| Tensor::new(rng.sample(rng_range()))).collect();
+            let bias = Tensor::new(rng.sample(rng_range()));
+            (weights, bias)
+        }).unzip();
+        Layer { weights, biases, activation }
     }
 
-    fn process(&self, inputs: &[Tensor]) -> Vec<Tensor> {
-        self.neurons.iter().map(|n| n.process(inputs)).collect()
+    /// Computes every neuron's `w·x + b`, for every example in the batch, in one `X·Wᵀ + b`
+    /// matmul instead of a per-example, per-neuron scalar loop, then wraps each resulting sum in
+    /// a `Tensor::affine` node so the scalar autograd graph still sees one node per neuron rather
+    /// than one per weight.
+    fn process(&self, batch: &[Vec<Tensor>]) -> Vec<Vec<Tensor>> {
+        let batch_size = batch.len();
+        let number_of_inputs = self.weights[0].len();
+        let number_of_neurons = self.weights.len();
+
+        let x = Matrix::new(
+            batch.iter().flat_map(|inputs| inputs.iter().map(|t| *t.data.borrow())).collect(),
+            (batch_size, number_of_inputs),
+        );
+        let w_t = Matrix::new(
+            (0..number_of_inputs).flat_map(|i| self.weights.iter().map(move |w| *w[i].data.borrow())).collect(),
+            (number_of_inputs, number_of_neurons),
+        );
+        let b = Matrix::new(self.biases.iter().map(|t| *t.data.borrow()).collect(), (1, number_of_neurons));
+        let sums = x.matmul(&w_t).add_bias_row(&b);
+        let sums_data = sums.data.borrow();
+
+        batch.iter().enumerate().map(|(row, inputs)| {
+            (0..number_of_neurons).map(|i| {
+                let pre_activation = Tensor::affine(inputs, &self.weights[i], &self.biases[i], sums_data[row * number_of_neurons + i]);
+                self.activation.apply(&pre_activation)
+            }).collect()
+        }).collect()
     }
 
     fn parameters(&self) -> Vec<Tensor> {
-        self.neurons.iter().flat_map(|n| n.parameters()).collect()
+        self.weights.iter().zip(self.biases.iter())
+            .flat_map(|(w, b)| [w.iter().map(Tensor::clone).collect(), vec![Tensor::clone(b)]].concat())
+            .collect()
     }
-
 }
 
 impl Mlp {
-    pub fn new(number_of_inputs: i32, layers: Vec<i32>, rng: &mut StdRng) -> Mlp {
-        let n = [vec![number_of_inputs], layers].concat();
+    pub fn new(number_of_inputs: i32, layers: Vec<(i32, Activation)>, rng: &mut StdRng) -> Mlp {
+        let n = [vec![(number_of_inputs, Activation::Identity)], layers].concat();
         let layers = n.iter().zip(n.iter().skip(1))
-            .map(|(n_inputs, n_neurons)| Layer::new(*n_inputs, *n_neurons, rng)).collect();
+            .map(|((n_inputs, _), (n_neurons, activation))| Layer::new(*n_inputs, *n_neurons, *activation, rng)).collect();
         Mlp { layers }
     }
 
-    pub fn process(&self, inputs: &[f64]) -> Vec<Tensor> {
-        let mut x: Vec<_> = inputs.iter().map(|i| Tensor::new(*i)).collect();
+    pub fn new_all_tanh(number_of_inputs: i32, layers: Vec<i32>, rng: &mut StdRng) -> Mlp {
+        Mlp::new(number_of_inputs, layers.into_iter().map(|n| (n, Activation::Tanh)).collect(), rng)
+    }
+
+    /// Runs a whole batch of examples through every layer, one matmul per layer per batch,
+    /// rather than looping over examples one at a time.
+    pub fn process(&self, batch: &[Vec<f64>]) -> Vec<Vec<Tensor>> {
+        let mut x: Vec<Vec<_>> = batch.iter().map(|inputs| inputs.iter().map(|i| Tensor::new(*i)).collect()).collect();
         for layer in &self.layers {
             x = layer.process(&x);
         }
@@ -88,52 +122,68 @@ mod tests {
     use crate::nn::*;
 
     #[test]
-    fn neuron_should_process_input() {
+    fn layer_should_process_a_batch_forwarding_every_example_to_all_neurons() {
         let mut rng = StdRng::seed_from_u64(42);
         let number_of_inputs = 3;
-        let neuron = Neuron::new(number_of_inputs, &mut rng);
+        let number_of_neurons = 3;
+        let layer = Layer::new(number_of_inputs, number_of_neurons, Activation::Tanh, &mut rng);
         let inputs: Vec<_> = (0..number_of_inputs).map(|_| Tensor::new(rng.gen())).collect();
 
-        let output = neuron.process(&inputs);
+        let output = layer.process(&[inputs]);
 
-        assert_eq!(*output.data.borrow(), 0.050308753080100216);
+        let outputs: Vec<_> = output[0].iter().map(|n| *n.data.borrow()).collect();
+        assert_eq!(outputs, vec![
+            -0.012655113950167263,
+            0.5073009752057568,
+            0.03629545163121696,
+        ]);
     }
 
     #[test]
-    fn layer_should_process_input_forwarding_to_all_neurons() {
+    fn layer_should_process_every_example_in_the_batch_independently() {
         let mut rng = StdRng::seed_from_u64(42);
         let number_of_inputs = 3;
         let number_of_neurons = 3;
-        let layer = Layer::new(number_of_inputs, number_of_neurons, &mut rng);
+        let layer = Layer::new(number_of_inputs, number_of_neurons, Activation::Tanh, &mut rng);
+        let example: Vec<_> = (0..number_of_inputs).map(|_| Tensor::new(rng.gen())).collect();
+        let batch = vec![example.clone(), example];
+
+        let output = layer.process(&batch);
+
+        let first: Vec<_> = output[0].iter().map(|n| *n.data.borrow()).collect();
+        let second: Vec<_> = output[1].iter().map(|n| *n.data.borrow()).collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn layer_should_apply_the_configured_activation() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let number_of_inputs = 3;
+        let layer = Layer::new(number_of_inputs, 1, Activation::Relu, &mut rng);
         let inputs: Vec<_> = (0..number_of_inputs).map(|_| Tensor::new(rng.gen())).collect();
 
-        let output = layer.process(&inputs);
+        let output = layer.process(&[inputs]);
 
-        let outputs: Vec<_> = output.iter().map(|n| *n.data.borrow()).collect();
-        assert_eq!(outputs, vec![
-            -0.012655113950167263,
-            0.5073009752057568,
-            0.03629545163121696,
-        ]);
+        assert!(*output[0][0].data.borrow() >= 0.0);
     }
 
     #[test]
-    fn mlp_should_process_inputs() {
+    fn mlp_should_process_a_batch_of_inputs() {
         let number_of_inputs = 3;
         let mut rng = StdRng::seed_from_u64(42);
-        let mlp = Mlp::new(number_of_inputs, vec![4, 4, 1], &mut rng);
+        let mlp = Mlp::new_all_tanh(number_of_inputs, vec![4, 4, 1], &mut rng);
 
         let inputs: Vec<_> = (0..number_of_inputs).map(|_| rng.gen()).collect();
-        let output = mlp.process(&inputs);
+        let output = mlp.process(&[inputs]);
 
-        assert_eq!(*output[0].data.borrow(), 0.88226677498484760);
+        assert_eq!(*output[0][0].data.borrow(), 0.88226677498484760);
     }
 
     #[test]
     fn mlp_should_return_all_parameters() {
         let number_of_inputs = 3;
         let mut rng = StdRng::seed_from_u64(42);
-        let mlp = Mlp::new(number_of_inputs, vec![4, 4, 1], &mut rng);
+        let mlp = Mlp::new_all_tanh(number_of_inputs, vec![4, 4, 1], &mut rng);
 
         assert_eq!(mlp.parameters().len(), 41);
     }
@@ -142,7 +192,7 @@ mod tests {
     fn mpl_should_zero_grad_all_parameters() {
         let number_of_inputs = 3;
         let mut rng = StdRng::seed_from_u64(42);
-        let mlp = Mlp::new(number_of_inputs, vec![4, 4, 1], &mut rng);
+        let mlp = Mlp::new_all_tanh(number_of_inputs, vec![4, 4, 1], &mut rng);
 
         for p in mlp.parameters() {
             *p.grad.borrow_mut() = 1.0;
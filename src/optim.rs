@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use uuid::Uuid;
+use crate::autograd::Tensor;
+
+pub trait Optimizer {
+    fn step(&mut self, params: &[Tensor]);
+}
+
+pub struct Sgd {
+    lr: f64,
+    momentum: f64,
+    velocity: HashMap<Uuid, f64>,
+}
+
+impl Sgd {
+    pub fn new(lr: f64, momentum: f64) -> Sgd {
+        Sgd { lr, momentum, velocity: HashMap::new() }
+    }
+}
+
+impl Optimizer for Sgd {
+    fn step(&mut self, params: &[Tensor]) {
+        for p in params {
+            let u = self.velocity.entry(p.label).or_insert(0.0);
+            *u = self.momentum * *u + *p.grad.borrow();
+            *p.data.borrow_mut() -= self.lr * *u;
+        }
+    }
+}
+
+pub struct Adam {
+    lr: f64,
+    beta1: f64,
+    beta2: f64,
+    eps: f64,
+    t: i32,
+    m: HashMap<Uuid, f64>,
+    v: HashMap<Uuid, f64>,
+}
+
+impl Adam {
+    pub fn new(lr: f64, beta1: f64, beta2: f64, eps: f64) -> Adam {
+        Adam { lr, beta1, beta2, eps, t: 0, m: HashMap::new(), v: HashMap::new() }
+    }
+}
+
+impl Optimizer for Adam {
+    fn step(&mut self, params: &[Tensor]) {
+        self.t += 1;
+        for p in params {
+            let g = *p.grad.borrow();
+
+            let m = self.m.entry(p.label).or_insert(0.0);
+            *m = self.beta1 * *m + (1.0 - self.beta1) * g;
+            let m_hat = *m / (1.0 - self.beta1.powi(self.t));
+
+            let v = self.v.entry(p.label).or_insert(0.0);
+            *v = self.beta2 * *v + (1.0 - self.beta2) * g * g;
+            let v_hat = *v / (1.0 - self.beta2.powi(self.t));
+
+            *p.data.borrow_mut() -= self.lr * m_hat / (v_hat.sqrt() + self.eps);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sgd_should_update_parameters_without_momentum() {
+        let mut sgd = Sgd::new(0.1, 0.0);
+        let p = Tensor::new_with_grad(1.0, 2.0);
+
+        sgd.step(&[Tensor::clone(&p)]);
+
+        assert_eq!(*p.data.borrow(), 0.8);
+    }
+
+    #[test]
+    fn sgd_should_accumulate_velocity_across_steps() {
+        let mut sgd = Sgd::new(0.1, 0.5);
+        let p = Tensor::new_with_grad(1.0, 1.0);
+
+        sgd.step(&[Tensor::clone(&p)]);
+        sgd.step(&[Tensor::clone(&p)]);
+
+        assert_eq!(*p.data.borrow(), 1.0 - 0.1 * 1.0 - 0.1 * 1.5);
+    }
+
+    #[test]
+    fn adam_should_update_parameters_using_bias_corrected_moments() {
+        let mut adam = Adam::new(0.01, 0.9, 0.999, 1e-8);
+        let p = Tensor::new_with_grad(1.0, 0.5);
+
+        adam.step(&[Tensor::clone(&p)]);
+
+        assert_eq!(*p.data.borrow(), 0.9900000002);
+    }
+}